@@ -1,26 +1,75 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::rc::Rc;
 
-struct HuffmanEncoding {
+struct HuffmanEncoding<T> {
     data: Vec<u64>,
     number_of_bits: u64,
-    map: HashMap<char, HuffmanUnitCode>
+    map: HashMap<T, HuffmanUnitCode>
 }
 
-struct HuffmanNode {
+struct HuffmanNode<T> {
     weight: u32,
-    symbol: Option<char>,
-    left: Option<Rc<HuffmanNode>>,
-    right: Option<Rc<HuffmanNode>>
+    symbol: Option<T>,
+    left: Option<Rc<HuffmanNode<T>>>,
+    right: Option<Rc<HuffmanNode<T>>>
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct HuffmanUnitCode {
     code: u64,
     number_of_bits: u8
 }
 
-impl HuffmanEncoding {
+// Magic prefix identifying a serialized HuffmanEncoding container.
+const MAGIC: &[u8; 4] = b"HUFF";
+
+#[derive(Debug, PartialEq)]
+enum DecodeError {
+    InvalidMagic,
+    UnexpectedEof,
+    InvalidSymbol,
+    HuffmanDecompressionFailed
+}
+
+// Incremental reader over the packed u64 buffer. Hands out one bit at a time
+// and refuses to read past the declared bit count instead of indexing out of
+// bounds.
+struct BitReader<'a> {
+    data: &'a [u64],
+    number_of_bits: u64,
+    position: u64
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u64], number_of_bits: u64) -> BitReader<'a> {
+        BitReader {
+            data,
+            number_of_bits,
+            position: 0
+        }
+    }
+
+    fn has_bits(&self) -> bool {
+        self.position < self.number_of_bits
+    }
+
+    fn read_bit(&mut self) -> Result<u8, DecodeError> {
+        if self.position >= self.number_of_bits {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        let array_index = (self.position / 64) as usize;
+        let bit_index = self.position % 64;
+        let word = self.data.get(array_index).ok_or(DecodeError::UnexpectedEof)?;
+        let bit = ((word >> bit_index) & 1) as u8;
+        self.position += 1;
+        Ok(bit)
+    }
+}
+
+impl<T: Copy + Ord + Hash> HuffmanEncoding<T> {
 
     fn add_value(&mut self, value: u64, number_of_bits: u64) {
         if self.number_of_bits & 63 == 0 {
@@ -39,29 +88,41 @@ impl HuffmanEncoding {
         self.number_of_bits += number_of_bits;
     }
 
-    fn encode(&mut self, text: &String) {
+    fn encode(&mut self, symbols: &[T]) {
+
+        self.map = self.get_huffman_encoding(symbols);
+        // Re-key to canonical codes so the per-symbol lengths alone reproduce
+        // the bitstream on reload (see to_bytes / from_code_lengths).
+        self.canonicalize();
+        let map = self.map.clone();
 
-        let map = self.get_huffman_encoding(text);
         self.data = Vec::new();
         self.number_of_bits = 0;
 
         let mut unit_code: &HuffmanUnitCode;
-        for c in text.chars() {
-            unit_code = map.get(&c).expect("character not found in map!");
+        for symbol in symbols {
+            unit_code = map.get(symbol).expect("symbol not found in map!");
             self.add_value(unit_code.code, unit_code.number_of_bits.into());
         }
-
-        self.map = map;
     }
 
-    fn get_huffman_encoding(&self, text: &String) -> HashMap<char, HuffmanUnitCode> {
-        let tree = self.construct_huffman_tree(text);
-        let encoding = self.get_encoding_from_huffman_tree(tree);
-        encoding
+    fn get_huffman_encoding(&self, symbols: &[T]) -> HashMap<T, HuffmanUnitCode> {
+        match self.construct_huffman_tree(symbols) {
+            Some(tree) => self.get_encoding_from_huffman_tree(tree),
+            None => HashMap::new()
+        }
     }
 
-    fn get_encoding_from_huffman_tree(&self, tree: Rc<HuffmanNode>) -> HashMap<char, HuffmanUnitCode> {
+    fn get_encoding_from_huffman_tree(&self, tree: Rc<HuffmanNode<T>>) -> HashMap<T, HuffmanUnitCode> {
         let mut result = HashMap::new();
+        if tree.left.is_none() && tree.right.is_none() {
+            // A lone symbol has no tree edge, so give it an explicit one-bit code.
+            result.insert(tree.symbol.unwrap(), HuffmanUnitCode {
+                code: 0,
+                number_of_bits: 1
+            });
+            return result;
+        }
         let curr_enc = HuffmanUnitCode {
             code: 0,
             number_of_bits: 0
@@ -70,12 +131,12 @@ impl HuffmanEncoding {
         result
     }
 
-    fn dfs_huffman_tree(&self, node: Rc<HuffmanNode>, curr_unit_code: HuffmanUnitCode, result: &mut HashMap<char, HuffmanUnitCode>) {
+    fn dfs_huffman_tree(&self, node: Rc<HuffmanNode<T>>, curr_unit_code: HuffmanUnitCode, result: &mut HashMap<T, HuffmanUnitCode>) {
         if node.left.is_none() && node.right.is_none() {
-            result.insert(node.symbol.clone().unwrap(), curr_unit_code);
+            result.insert(node.symbol.unwrap(), curr_unit_code);
             return;
         }
-    
+
         if !node.left.is_none() {
             let mut left_unit_code = HuffmanUnitCode {
                 code: curr_unit_code.code,
@@ -92,40 +153,48 @@ impl HuffmanEncoding {
             right_unit_code.add_msb(1);
             self.dfs_huffman_tree(node.right.clone().unwrap(), right_unit_code, result);
         }
-    
+
     }
 
-    fn construct_huffman_tree(&self, text: &String) -> Rc<HuffmanNode> {
-        let mut leaves = self.get_huffman_leaves(text);
-        quick_sort(&mut leaves, &|x,y| x.weight < y.weight);
-        let mut intermediate_nodes = Vec::new();
-        while leaves.len() > 0 || intermediate_nodes.len() > 1 {
-            let one = take_smallest(&mut leaves, &mut intermediate_nodes);
-            let two = take_smallest(&mut leaves, &mut intermediate_nodes);
-            let new_node = HuffmanNode {
+    fn construct_huffman_tree(&self, symbols: &[T]) -> Option<Rc<HuffmanNode<T>>> {
+        let mut heap: BinaryHeap<HuffmanNode<T>> = BinaryHeap::new();
+        // Push leaves in symbol order: HashMap iteration order varies per run,
+        // and it would otherwise decide ties between merged (symbol-less) nodes.
+        let mut leaves = self.get_huffman_leaves(symbols);
+        leaves.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        for leaf in leaves {
+            heap.push(leaf);
+        }
+        if heap.is_empty() {
+            return None;
+        }
+
+        while heap.len() > 1 {
+            let one = heap.pop().unwrap();
+            let two = heap.pop().unwrap();
+            heap.push(HuffmanNode {
                 weight: one.weight + two.weight,
                 symbol: None,
                 left: Some(Rc::new(one)),
                 right: Some(Rc::new(two))
-            };
-            intermediate_nodes.push(new_node);
+            });
         }
-    
-        Rc::new(intermediate_nodes.pop().unwrap())
+
+        Some(Rc::new(heap.pop().unwrap()))
     }
 
-    fn compute_frequencies(&self, text: &String) -> HashMap<char, u32> {
+    fn compute_frequencies(&self, symbols: &[T]) -> HashMap<T, u32> {
         let mut result = HashMap::new();
-        for c in text.chars() {
-            let count = result.entry(c).or_insert(0);
+        for symbol in symbols {
+            let count = result.entry(*symbol).or_insert(0);
             *count += 1;
         }
-    
+
         result
     }
-    
-    fn get_huffman_leaves(&self, text: &String) -> Vec<HuffmanNode> {
-        let freqs = self.compute_frequencies(text);
+
+    fn get_huffman_leaves(&self, symbols: &[T]) -> Vec<HuffmanNode<T>> {
+        let freqs = self.compute_frequencies(symbols);
         let mut leaves = Vec::new();
         for (symbol, freq) in freqs {
             leaves.push(HuffmanNode {
@@ -138,52 +207,258 @@ impl HuffmanEncoding {
         leaves
     }
 
-    fn decode(&self) -> String {
-        let mut result = String::new();
-        let rev_map: HashMap<HuffmanUnitCode, char> = self.reverse_encoding_map(&self.map);
-        let mut array_index = 0;
-        let mut bit_index = 0;
+    fn decode(&self) -> Result<Vec<T>, DecodeError> {
+        let mut result = Vec::new();
+        let rev_map: HashMap<HuffmanUnitCode, T> = self.reverse_encoding_map(&self.map);
+        let mut reader = BitReader::new(&self.data, self.number_of_bits);
+        // Longest codeword: once the accumulator passes this without a match the
+        // stream is corrupt, and stopping here also keeps add_msb from shifting
+        // past the word width.
+        let max_len = self.map.values().map(|code| code.number_of_bits).max().unwrap_or(0);
         let mut unit_code = HuffmanUnitCode {
             code: 0,
             number_of_bits: 0
         };
-        let mut i = 0;
-        while array_index < self.data.len() && i < self.number_of_bits {
-            if self.data.get(array_index).expect("Out of bound during decoding") & (1 << bit_index) == 0 {
-                unit_code.add_msb(0);
-            } else {
-                unit_code.add_msb(1);
-            }
-
-            bit_index = (bit_index + 1) % 64;
-            if bit_index == 0 {
-                array_index += 1;
-            }
+        while reader.has_bits() {
+            let bit = reader.read_bit()?;
+            unit_code.add_msb(bit as u64);
             match rev_map.get(&unit_code) {
                 Some(&c) => {
                     result.push(c);
                     unit_code.code = 0;
                     unit_code.number_of_bits = 0;
                 },
-                None => ()
+                None => {
+                    if unit_code.number_of_bits >= max_len {
+                        return Err(DecodeError::HuffmanDecompressionFailed);
+                    }
+                }
             }
+        }
 
-            i += 1;
+        // A dangling partial code means the bits never matched any codeword.
+        if unit_code.number_of_bits != 0 {
+            return Err(DecodeError::HuffmanDecompressionFailed);
         }
+        self.verify_ending()?;
+        Ok(result)
+    }
 
-        result
+    // The bits past number_of_bits in the final word must be pure padding:
+    // either all zeros or all ones up to the word boundary.
+    fn verify_ending(&self) -> Result<(), DecodeError> {
+        let used = (self.number_of_bits % 64) as u32;
+        if used == 0 {
+            return Ok(());
+        }
+        let last = match self.data.last() {
+            Some(word) => *word,
+            None => return Ok(())
+        };
+        let padding = last >> used;
+        let all_ones = padding == (u64::MAX >> used);
+        if padding == 0 || all_ones {
+            Ok(())
+        } else {
+            Err(DecodeError::HuffmanDecompressionFailed)
+        }
     }
 
-    fn reverse_encoding_map(&self, map: &HashMap<char, HuffmanUnitCode>) -> HashMap<HuffmanUnitCode, char> {
+    fn reverse_encoding_map(&self, map: &HashMap<T, HuffmanUnitCode>) -> HashMap<HuffmanUnitCode, T> {
         let mut result = HashMap::new();
         for (symbol, code) in map {
-            result.insert(code.clone(), symbol.clone());
+            result.insert(code.clone(), *symbol);
+        }
+
+        result
+    }
+
+    // Rebuild self.map canonically from the per-symbol bit lengths the tree
+    // DFS already assigned. The codes become fully determined by the lengths,
+    // so only the lengths need to travel alongside the data.
+    fn canonicalize(&mut self) {
+        let mut lengths = HashMap::new();
+        for (symbol, code) in &self.map {
+            lengths.insert(*symbol, code.number_of_bits);
+        }
+        self.map = Self::build_canonical_map(&lengths);
+    }
+
+    // Decode-side constructor: rebuild the exact same map from just the lengths.
+    fn from_code_lengths(lengths: &HashMap<T, u8>) -> HuffmanEncoding<T> {
+        HuffmanEncoding {
+            data: Vec::new(),
+            number_of_bits: 0,
+            map: Self::build_canonical_map(lengths)
+        }
+    }
+
+    fn build_canonical_map(lengths: &HashMap<T, u8>) -> HashMap<T, HuffmanUnitCode> {
+        let mut pairs: Vec<(T, u8)> = lengths.iter().map(|(&s, &l)| (s, l)).collect();
+        // A single distinct symbol has no tree edge, so force it to one bit.
+        if pairs.len() == 1 {
+            pairs[0].1 = 1;
+        }
+        pairs.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+
+        let mut result = HashMap::new();
+        let mut code: u64 = 0;
+        let mut prev_len: u8 = 0;
+        for (i, &(symbol, len)) in pairs.iter().enumerate() {
+            if i > 0 {
+                code = (code + 1) << (len - prev_len);
+            }
+            // Canonical codes are prefix-free read MSB-first, but add_value /
+            // decode pack and read bits LSB-first within the integer. Store the
+            // bits reversed so the set stays prefix-free in the reader's order.
+            result.insert(symbol, HuffmanUnitCode {
+                code: reverse_code_bits(code, len),
+                number_of_bits: len
+            });
+            prev_len = len;
         }
-    
         result
     }
 }
 
+fn reverse_code_bits(code: u64, len: u8) -> u64 {
+    let mut result = 0;
+    for i in 0..len {
+        if (code >> i) & 1 == 1 {
+            result |= 1 << (len - 1 - i);
+        }
+    }
+    result
+}
+
+// Thin char/String wrapper over the generic machinery.
+impl HuffmanEncoding<char> {
+    fn encode_str(&mut self, text: &String) {
+        let symbols: Vec<char> = text.chars().collect();
+        self.encode(&symbols);
+    }
+
+    fn decode_str(&self) -> Result<String, DecodeError> {
+        Ok(self.decode()?.into_iter().collect())
+    }
+
+    // Build an encoding around a fixed, pre-agreed table of
+    // (symbol, code, bit-length) entries. Both sides share this table, so no
+    // tree is built and no per-message header needs to travel.
+    //
+    // Codes are read LSB-first within the integer (the first transmitted bit is
+    // bit 0), so the table must be prefix-free in that order. A table taken from
+    // an MSB-first standard such as the RFC 7541 HPACK table must have each code
+    // bit-reversed within its length before being passed here.
+    fn with_static_table(table: &[(char, u64, u8)]) -> HuffmanEncoding<char> {
+        let mut map = HashMap::new();
+        for &(symbol, code, number_of_bits) in table {
+            map.insert(symbol, HuffmanUnitCode {
+                code,
+                number_of_bits
+            });
+        }
+        HuffmanEncoding {
+            data: Vec::new(),
+            number_of_bits: 0,
+            map
+        }
+    }
+
+    // Encode against the static table, skipping frequency counting and tree
+    // construction entirely.
+    fn encode_static(&mut self, text: &String) {
+        self.data = Vec::new();
+        self.number_of_bits = 0;
+        for c in text.chars() {
+            let unit_code = *self.map.get(&c).expect("symbol not found in static table!");
+            self.add_value(unit_code.code, unit_code.number_of_bits.into());
+        }
+    }
+
+    fn decode_static(&self) -> Result<String, DecodeError> {
+        self.decode_str()
+    }
+
+    // Serialize into a self-describing container: magic bytes, the total bit
+    // count, the symbol count, the per-symbol code-length table, then the
+    // packed bitstream. Only one length byte per symbol travels, since the
+    // canonical codes are fully determined by the lengths on reload.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&self.number_of_bits.to_le_bytes());
+        out.extend_from_slice(&(self.map.len() as u32).to_le_bytes());
+
+        // Emit the table in symbol order so the output is stable.
+        let mut table: Vec<(char, u8)> = self.map.iter()
+            .map(|(&symbol, code)| (symbol, code.number_of_bits))
+            .collect();
+        table.sort_by_key(|e| e.0);
+        for (symbol, length) in table {
+            out.extend_from_slice(&(symbol as u32).to_le_bytes());
+            out.push(length);
+        }
+
+        for word in &self.data {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<HuffmanEncoding<char>, DecodeError> {
+        if bytes.len() < MAGIC.len() || &bytes[0..MAGIC.len()] != MAGIC {
+            return Err(DecodeError::InvalidMagic);
+        }
+        let mut offset = MAGIC.len();
+
+        let number_of_bits = read_u64(bytes, &mut offset)?;
+        let symbol_count = read_u32(bytes, &mut offset)?;
+
+        let mut lengths = HashMap::new();
+        for _ in 0..symbol_count {
+            let raw = read_u32(bytes, &mut offset)?;
+            let symbol = char::from_u32(raw).ok_or(DecodeError::InvalidSymbol)?;
+            let length = *bytes.get(offset).ok_or(DecodeError::UnexpectedEof)?;
+            offset += 1;
+            lengths.insert(symbol, length);
+        }
+
+        let word_count = number_of_bits.div_ceil(64) as usize;
+        let mut data = Vec::with_capacity(word_count);
+        for _ in 0..word_count {
+            data.push(read_u64(bytes, &mut offset)?);
+        }
+
+        let mut encoding = HuffmanEncoding::<char>::from_code_lengths(&lengths);
+        encoding.data = data;
+        encoding.number_of_bits = number_of_bits;
+        Ok(encoding)
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: &mut usize) -> Result<u32, DecodeError> {
+    let end = *offset + 4;
+    if end > bytes.len() {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&bytes[*offset..end]);
+    *offset = end;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(bytes: &[u8], offset: &mut usize) -> Result<u64, DecodeError> {
+    let end = *offset + 8;
+    if end > bytes.len() {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[*offset..end]);
+    *offset = end;
+    Ok(u64::from_le_bytes(buf))
+}
+
 impl HuffmanUnitCode {
     fn add_bit_zero(&mut self) {
         self.code = self.code << 1;
@@ -196,7 +471,6 @@ impl HuffmanUnitCode {
     }
 
     fn add_msb(&mut self, bit: u64) {
-        println!("Adding {} to code {}, number_of_bits {}", bit, self.code, self.number_of_bits);
         let mask = !(1 << self.number_of_bits);
         self.code = self.code & mask; // set the new msb to 0
         let msb = bit << self.number_of_bits;
@@ -205,49 +479,33 @@ impl HuffmanUnitCode {
     }
 }
 
-fn take_smallest(first: &mut Vec<HuffmanNode>, second: &mut Vec<HuffmanNode>) -> HuffmanNode {
-    if first.is_empty() || (!second.is_empty() && first[0].weight > second[0].weight) {
-        second.remove(0)
-    } else {
-        first.remove(0)
+// Order nodes so the BinaryHeap behaves as a min-heap on weight: the lightest
+// node must compare as the greatest so it pops first. Ties break on the symbol
+// so tree construction is deterministic across runs.
+impl<T: Ord> Ord for HuffmanNode<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.weight.cmp(&self.weight)
+            .then_with(|| other.symbol.cmp(&self.symbol))
     }
 }
 
-
-fn main() {
-    println!("Hello, world!!!");
+impl<T: Ord> PartialOrd for HuffmanNode<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-fn quick_sort<T,F>(v: &mut [T], f: &F)
-    where F: Fn(&T,&T) -> bool
-{
-    let len = v.len();
-    if len >= 2 {
-        let pivot_index = partition(v, f);
-        quick_sort(&mut v[0..pivot_index], f);
-        quick_sort(&mut v[pivot_index + 1..len], f);
+impl<T: Ord> PartialEq for HuffmanNode<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.weight == other.weight && self.symbol == other.symbol
     }
 }
 
-fn partition<T,F>(v: &mut [T], f: &F) -> usize
-    where F: Fn(&T,&T) -> bool
-{
-    let len = v.len();
-    let pivot_index = len / 2;
-    let last_index = len - 1;
+impl<T: Ord> Eq for HuffmanNode<T> {}
 
-    v.swap(pivot_index, last_index);
 
-    let mut store_index = 0;
-    for i in 0..last_index {
-        if f(&v[i], &v[last_index]) {
-            v.swap(i, store_index);
-            store_index += 1;
-        }
-    }
-
-    v.swap(store_index, len - 1);
-    store_index
+fn main() {
+    println!("Hello, world!!!");
 }
 
 #[cfg(test)]
@@ -258,6 +516,7 @@ mod test {
     use super::HuffmanUnitCode;
     //use super::get_huffman_encoding;
     use super::HuffmanEncoding;
+    use super::DecodeError;
     //use super::encode_with_huffman;
     //use super::reverse_encoding_map;
     use std::collections::HashMap;
@@ -349,7 +608,7 @@ mod test {
 
     #[test]
     fn test_add_value_huffman_encoding() {
-        let mut enc = HuffmanEncoding {
+        let mut enc: HuffmanEncoding<char> = HuffmanEncoding {
             data: Vec::new(),
             number_of_bits: 0,
             map: HashMap::new()
@@ -385,7 +644,7 @@ mod test {
 //        let mut map = HashMap::new();
 //        map.insert('a', code_1);
 //        map.insert('b', code_2);
-//        
+//
 //        let result = reverse_encoding_map(&map);
 //        assert_eq!(result.get(&code_1), Some(&'a'));
 //        assert_eq!(result.get(&code_2), Some(&'b'));
@@ -418,14 +677,14 @@ mod test {
     #[test]
     fn test_huffman_encode_decode() {
         //let text = String::from("Hello, what a nice day it is today");
-        let mut huffman = HuffmanEncoding {
+        let mut huffman: HuffmanEncoding<char> = HuffmanEncoding {
             data: Vec::new(),
             number_of_bits: 0,
             map: HashMap::new()
         };
         let text = String::from("What a nice weather you have here in Greece");
         //let huffman = encode_with_huffman(&text);
-        huffman.encode(&text);
+        huffman.encode_str(&text);
 
         for (symbol, code) in &huffman.map {
             println!("symbol={}, code={:#0b}, number_of_bits={}", symbol, code.code, code.number_of_bits);
@@ -433,6 +692,65 @@ mod test {
         for e in &huffman.data {
             println!("e={:#0b}", e);
         }
-        assert_eq!(huffman.decode(), text);
+        assert_eq!(huffman.decode_str().unwrap(), text);
+    }
+
+    #[test]
+    fn test_from_code_lengths_reproduces_map() {
+        let mut huffman: HuffmanEncoding<char> = HuffmanEncoding {
+            data: Vec::new(),
+            number_of_bits: 0,
+            map: HashMap::new()
+        };
+        let text = String::from("mmmmaaarrrthhaa");
+        huffman.encode_str(&text);
+
+        let mut lengths = HashMap::new();
+        for (symbol, code) in &huffman.map {
+            lengths.insert(*symbol, code.number_of_bits);
+        }
+        let rebuilt = HuffmanEncoding::<char>::from_code_lengths(&lengths);
+        assert_eq!(rebuilt.map, huffman.map);
+    }
+
+    #[test]
+    fn test_decode_rejects_corrupt_input() {
+        let mut map = HashMap::new();
+        map.insert('a', HuffmanUnitCode {
+            code: 3,
+            number_of_bits: 2
+        });
+        // Two zero bits match no codeword, so decoding must fail rather than panic.
+        let huffman: HuffmanEncoding<char> = HuffmanEncoding {
+            data: vec![0],
+            number_of_bits: 2,
+            map
+        };
+        assert_eq!(huffman.decode_str(), Err(DecodeError::HuffmanDecompressionFailed));
+    }
+
+    #[test]
+    fn test_to_bytes_from_bytes_round_trip() {
+        let mut huffman: HuffmanEncoding<char> = HuffmanEncoding {
+            data: Vec::new(),
+            number_of_bits: 0,
+            map: HashMap::new()
+        };
+        let text = String::from("mmmmaaarrrthhaa");
+        huffman.encode_str(&text);
+
+        let bytes = huffman.to_bytes();
+        let restored = HuffmanEncoding::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.decode_str().unwrap(), text);
+    }
+
+    #[test]
+    fn test_static_table_round_trip() {
+        // Prefix-free in LSB-first order: 'a' starts with bit 0, 'b'/'c' with bit 1.
+        let table = [('a', 0u64, 1u8), ('b', 1, 2), ('c', 3, 2)];
+        let mut huffman = HuffmanEncoding::with_static_table(&table);
+        let text = String::from("abcabcaa");
+        huffman.encode_static(&text);
+        assert_eq!(huffman.decode_static().unwrap(), text);
     }
 }